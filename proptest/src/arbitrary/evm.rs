@@ -9,7 +9,8 @@
 
 use primitive_types::{H128, H160, H256, H512, U128, U256, U512};
 
-use crate::strategy::Map;
+use crate::std_facade::vec::Vec;
+use crate::strategy::{BoxedStrategy, Map, Union};
 
 use super::Strategy;
 use super::{any, Arbitrary, StrategyFor};
@@ -18,8 +19,7 @@ macro_rules! hash_impl {
     ($t:ty, $bytes:literal) => {
         impl Arbitrary for $t {
             type Parameters = ();
-            type Strategy =
-                Map<StrategyFor<[u8; $bytes]>, fn([u8; $bytes]) -> Self>;
+            type Strategy = Map<StrategyFor<[u8; $bytes]>, fn([u8; $bytes]) -> Self>;
 
             fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
                 any::<[u8; $bytes]>().prop_map(|bytes| <$t>::from_slice(&bytes))
@@ -28,15 +28,79 @@ macro_rules! hash_impl {
     };
 }
 
+/// Values of `[u64; N]` that sit on or near the boundaries of the
+/// (`64 * N`)-bit space: zero, one, the maximum, the maximum minus one,
+/// small powers of two, and single-bit / all-low-bits-set patterns at each
+/// word boundary.
+///
+/// Arithmetic bugs in code built on these wide integers disproportionately
+/// live at exactly these edges, which a uniform sample over the full range
+/// essentially never reaches.
+fn edge_case_patterns<const N: usize>() -> Vec<[u64; N]> {
+    let mut patterns = vec![[0u64; N], [u64::MAX; N]];
+
+    let mut one = [0u64; N];
+    one[0] = 1;
+    patterns.push(one);
+
+    let mut max_minus_one = [u64::MAX; N];
+    max_minus_one[0] = u64::MAX - 1;
+    patterns.push(max_minus_one);
+
+    for shift in [1u32, 2, 4, 8, 16, 32, 63] {
+        let mut small = [0u64; N];
+        small[0] = 1u64 << shift;
+        patterns.push(small);
+    }
+
+    for word in 0..N {
+        let mut single_bit = [0u64; N];
+        single_bit[word] = 1;
+        patterns.push(single_bit);
+
+        let mut low_bits_set = [0u64; N];
+        low_bits_set[..=word].fill(u64::MAX);
+        patterns.push(low_bits_set);
+    }
+
+    patterns
+}
+
+/// A strategy over `[u64; N]` that, with meaningful probability, produces one
+/// of [`edge_case_patterns`] instead of a uniformly random value, the way a
+/// fuzzer biases its input toward interesting boundaries. Shrinking still
+/// drives the value toward `0` as usual, since both the edge-case index and
+/// the uniform fallback shrink toward their own zero value.
+fn edge_biased_words<const N: usize>() -> BoxedStrategy<[u64; N]> {
+    let patterns = edge_case_patterns::<N>();
+    let num_patterns = patterns.len() as u32;
+
+    Union::new(vec![
+        // The edge-case arm's own weight of `num_patterns` is then split
+        // uniformly across its `num_patterns` patterns, so the uniform arm
+        // here is `3 * num_patterns` times as likely as any *single* edge
+        // case, not merely three times. The two arms are still a plain 3:1
+        // split overall, so the aggregate chance of landing on *some* edge
+        // case stays ~25%.
+        (num_patterns * 3, any::<[u64; N]>().boxed()),
+        (
+            num_patterns,
+            (0usize..patterns.len())
+                .prop_map(move |i| patterns[i])
+                .boxed(),
+        ),
+    ])
+    .boxed()
+}
+
 macro_rules! prim_impl {
     ($t:ty, $u64s:literal) => {
         impl Arbitrary for $t {
             type Parameters = ();
-            type Strategy =
-                Map<StrategyFor<[u64; $u64s]>, fn([u64; $u64s]) -> Self>;
+            type Strategy = Map<BoxedStrategy<[u64; $u64s]>, fn([u64; $u64s]) -> Self>;
 
             fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
-                any::<[u64; $u64s]>().prop_map(Self)
+                edge_biased_words::<$u64s>().prop_map(Self)
             }
         }
     };
@@ -51,3 +115,52 @@ prim_impl!(U128, 2);
 prim_impl!(U256, 4);
 prim_impl!(U512, 8);
 
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::strategy::ValueTree;
+    use crate::test_runner::{Config, TestRunner};
+
+    #[test]
+    fn edge_patterns_are_reachable() {
+        let strategy = edge_biased_words::<4>();
+        let mut runner = TestRunner::new(Config::default());
+        let patterns = edge_case_patterns::<4>();
+        let zero = patterns[0];
+        let all_ones = patterns[1];
+
+        let mut seen_zero = false;
+        let mut seen_all_ones = false;
+        for _ in 0..4096 {
+            let value = strategy.new_tree(&mut runner).unwrap().current();
+            seen_zero |= value == zero;
+            seen_all_ones |= value == all_ones;
+            if seen_zero && seen_all_ones {
+                break;
+            }
+        }
+
+        assert!(seen_zero, "never generated the all-zero edge case");
+        assert!(seen_all_ones, "never generated the all-ones edge case");
+    }
+
+    #[test]
+    fn shrinks_toward_zero() {
+        let strategy = edge_biased_words::<4>();
+        let mut runner = TestRunner::new(Config::default());
+
+        let mut cases_tested = 0;
+        for _ in 0..256 {
+            let mut tree = strategy.new_tree(&mut runner).unwrap();
+            if tree.current() == [0u64; 4] {
+                continue;
+            }
+
+            while tree.simplify() {}
+            assert_eq!([0u64; 4], tree.current());
+            cases_tested += 1;
+        }
+
+        assert!(cases_tested > 0, "Didn't find enough non-zero test cases");
+    }
+}
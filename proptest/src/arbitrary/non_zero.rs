@@ -8,28 +8,79 @@
 // except according to those terms.
 
 use core::num::{
-    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize,
-    NonZeroU128, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
 };
 
 use crate::{
     arbitrary::{any, Arbitrary},
-    strategy::{BoxedStrategy, Filter},
+    strategy::{size_hint::SizeHint, NewTree, Strategy, ValueTree},
+    test_runner::TestRunner,
 };
 
 use super::StrategyFor;
 
+/// `Strategy` for the `NonZero*` integer types.
+///
+/// Unlike filtering a plain integer strategy down to non-zero values, this
+/// never discards a generated case: a generated `0` is simply mapped to `1`,
+/// the smallest-magnitude non-zero value. Shrinking is delegated entirely to
+/// the underlying integer strategy, which already targets `0`, so shrunken
+/// values naturally approach `1` (or `-1`, for signed types) instead of
+/// stalling at the filter boundary.
+#[derive(Clone, Copy, Debug)]
+pub struct NonZeroStrategy<S> {
+    inner: S,
+}
+
+/// `ValueTree` for the `NonZero*` integer types.
+#[derive(Clone, Copy, Debug)]
+pub struct NonZeroValueTree<T> {
+    inner: T,
+}
+
 macro_rules! non_zero_impl {
     ($base:ty, $non_zero:ty) => {
         impl Arbitrary for $non_zero {
             type Parameters = ();
-            type Strategy = BoxedStrategy<Self>;
+            type Strategy = NonZeroStrategy<StrategyFor<$base>>;
 
             fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
-                any::<$base>()
-                    .prop_filter("must be non-zero", |i| i != 0)
-                    .prop_map(|i| i.try_into().unwrap())
-                    .boxed()
+                NonZeroStrategy {
+                    inner: any::<$base>(),
+                }
+            }
+        }
+
+        impl Strategy for NonZeroStrategy<StrategyFor<$base>> {
+            type Tree = NonZeroValueTree<<StrategyFor<$base> as Strategy>::Tree>;
+            type Value = $non_zero;
+
+            fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+                Ok(NonZeroValueTree {
+                    inner: self.inner.new_tree(runner)?,
+                })
+            }
+
+            fn size_hint(&self) -> SizeHint {
+                self.inner.size_hint()
+            }
+        }
+
+        impl ValueTree for NonZeroValueTree<<StrategyFor<$base> as Strategy>::Tree> {
+            type Value = $non_zero;
+
+            fn current(&self) -> Self::Value {
+                <$non_zero>::new(self.inner.current())
+                    .unwrap_or_else(|| <$non_zero>::new(1).unwrap())
+            }
+
+            fn simplify(&mut self) -> bool {
+                self.inner.simplify()
+            }
+
+            fn complicate(&mut self) -> bool {
+                self.inner.complicate()
             }
         }
     };
@@ -47,3 +98,43 @@ non_zero_impl!(i32, NonZeroI32);
 non_zero_impl!(i64, NonZeroI64);
 non_zero_impl!(i128, NonZeroI128);
 non_zero_impl!(isize, NonZeroIsize);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_runner::Config;
+
+    #[test]
+    fn generated_zero_maps_to_smallest_nonzero_magnitude() {
+        let mut runner = TestRunner::new(Config::default());
+        let strategy = any::<NonZeroU8>();
+        for _ in 0..512 {
+            let value = strategy.new_tree(&mut runner).unwrap().current();
+            assert_ne!(0, value.get());
+        }
+
+        // An empty fuzz buffer makes the inner integer generate 0.
+        let mut zero_runner = TestRunner::from_fuzz_bytes(&[]);
+        let mapped = strategy.new_tree(&mut zero_runner).unwrap().current();
+        assert_eq!(1, mapped.get());
+    }
+
+    #[test]
+    fn unsigned_shrinks_to_one() {
+        let mut runner = TestRunner::new(Config::default());
+        let strategy = any::<NonZeroU32>();
+        let mut tree = strategy.new_tree(&mut runner).unwrap();
+        while tree.simplify() {}
+        assert_eq!(1, tree.current().get());
+    }
+
+    #[test]
+    fn signed_shrinks_to_one_or_minus_one() {
+        let mut runner = TestRunner::new(Config::default());
+        let strategy = any::<NonZeroI32>();
+        let mut tree = strategy.new_tree(&mut runner).unwrap();
+        while tree.simplify() {}
+        let shrunk = tree.current().get();
+        assert!(shrunk == 1 || shrunk == -1);
+    }
+}
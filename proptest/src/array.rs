@@ -24,6 +24,7 @@ use core::convert::TryInto;
 use core::iter;
 use core::marker::PhantomData;
 
+use crate::strategy::size_hint::{self, SizeHint};
 use crate::strategy::*;
 use crate::test_runner::*;
 
@@ -92,9 +93,7 @@ pub struct ArrayValueTree<T> {
 ///
 /// See [`UniformArrayStrategy`](struct.UniformArrayStrategy.html) for
 /// example usage.
-pub fn uniform<S: Strategy, const N: usize>(
-    strategy: S,
-) -> UniformArrayStrategy<S, [S::Value; N]> {
+pub fn uniform<S: Strategy, const N: usize>(strategy: S) -> UniformArrayStrategy<S, [S::Value; N]> {
     UniformArrayStrategy {
         strategy,
         _marker: PhantomData,
@@ -106,6 +105,8 @@ impl<S: Strategy, const N: usize> Strategy for [S; N] {
     type Value = [S::Value; N];
 
     fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        runner.check_size_budget(self.size_hint())?;
+
         let tree_vec = self
             .iter()
             .map(|strategy| strategy.new_tree(runner))
@@ -122,15 +123,21 @@ impl<S: Strategy, const N: usize> Strategy for [S; N] {
             last_shrinker: None,
         })
     }
+
+    fn size_hint(&self) -> SizeHint {
+        self.iter()
+            .map(Strategy::size_hint)
+            .fold(size_hint::exact(0), size_hint::and)
+    }
 }
 
-impl<S: Strategy, const N: usize> Strategy
-    for UniformArrayStrategy<S, [S::Value; N]>
-{
+impl<S: Strategy, const N: usize> Strategy for UniformArrayStrategy<S, [S::Value; N]> {
     type Tree = ArrayValueTree<[S::Tree; N]>;
     type Value = [S::Value; N];
 
     fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        runner.check_size_budget(self.size_hint())?;
+
         let tree_vec = iter::repeat_with(|| self.strategy.new_tree(runner))
             .take(N)
             .collect::<Result<Vec<_>, _>>()?;
@@ -146,6 +153,10 @@ impl<S: Strategy, const N: usize> Strategy
             last_shrinker: None,
         })
     }
+
+    fn size_hint(&self) -> SizeHint {
+        size_hint::and_all_n(self.strategy.size_hint(), N)
+    }
 }
 
 impl<T: ValueTree, const N: usize> ValueTree for ArrayValueTree<[T; N]> {
@@ -0,0 +1,308 @@
+//-
+// Copyright 2024 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Strategies for generating `std::collections`.
+
+use crate::std_facade::vec::Vec;
+
+use core::ops::Range;
+
+use crate::strategy::size_hint::{self, SizeHint};
+use crate::strategy::*;
+use crate::test_runner::*;
+
+/// Create a strategy for `Vec`s whose length is not chosen up front but is
+/// instead however many elements fit in the remaining input entropy.
+///
+/// Under the fuzz-bytes `TestRng` (see
+/// [`TestRunner::from_fuzz_bytes`](crate::test_runner::TestRunner::from_fuzz_bytes)),
+/// this keeps drawing elements until the underlying byte buffer is
+/// exhausted, mirroring `arbitrary`'s `arbitrary_take_rest`: it uses the
+/// fuzzer's input more efficiently than a length-prefixed `Vec` strategy and
+/// tends to produce longer, more varied collections. Under an ordinary PRNG,
+/// where there is no natural "end of input" to run out of, the length is
+/// instead drawn from `fallback_size`.
+///
+/// The resulting `ValueTree` shrinks by first dropping elements from the
+/// tail, then shrinking the values that remain.
+pub fn vec_take_rest<T: Strategy>(
+    element: T,
+    fallback_size: Range<usize>,
+) -> VecTakeRestStrategy<T> {
+    VecTakeRestStrategy {
+        element,
+        fallback_size,
+    }
+}
+
+/// Strategy created by [`vec_take_rest`].
+#[derive(Clone, Debug)]
+pub struct VecTakeRestStrategy<T> {
+    element: T,
+    fallback_size: Range<usize>,
+}
+
+/// `ValueTree` produced by [`VecTakeRestStrategy`].
+pub struct VecTakeRestValueTree<T: ValueTree> {
+    elements: Vec<T>,
+    removed: Vec<T>,
+    phase: ShrinkPhase,
+}
+
+enum ShrinkPhase {
+    DeleteTail,
+    ShrinkElements(usize),
+}
+
+impl<T: Strategy> Strategy for VecTakeRestStrategy<T> {
+    type Tree = VecTakeRestValueTree<T::Tree>;
+    type Value = Vec<T::Value>;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        let mut elements = Vec::new();
+        let max_elements = runner
+            .config()
+            .max_value_size
+            .map(|budget| budget / self.element.size_hint().0.max(1));
+
+        if runner.rng().is_byte_driven() {
+            while !runner.rng().fuzz_bytes_exhausted() {
+                if max_elements.is_some_and(|max| elements.len() >= max) {
+                    break;
+                }
+
+                let remaining_before = runner.rng().fuzz_bytes_remaining();
+                elements.push(self.element.new_tree(runner)?);
+
+                // An element strategy that consumes no bytes (e.g. `Just`)
+                // would otherwise keep the buffer from ever reporting
+                // exhausted, looping forever; stop growing instead once an
+                // iteration makes no progress against it.
+                if runner.rng().fuzz_bytes_remaining() == remaining_before {
+                    break;
+                }
+            }
+        } else {
+            let span = (self.fallback_size.end.max(self.fallback_size.start + 1)
+                - self.fallback_size.start) as u64;
+            let mut len = self.fallback_size.start + runner.rng().choose(span) as usize;
+            if let Some(max_elements) = max_elements {
+                len = len.min(max_elements);
+            }
+            for _ in 0..len {
+                elements.push(self.element.new_tree(runner)?);
+            }
+        }
+
+        Ok(VecTakeRestValueTree {
+            elements,
+            removed: Vec::new(),
+            phase: ShrinkPhase::DeleteTail,
+        })
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        // `size_hint` can't know which `TestRng` mode `new_tree` will run
+        // under: under the fuzz-bytes RNG, `new_tree`'s exhaustion check can
+        // stop generation at 0 elements regardless of `fallback_size`, so
+        // `fallback_size.start` is not actually guaranteed. 0 is the only
+        // sound lower bound; overstating it here would make a parent
+        // strategy's summed bound spuriously large and could trip
+        // `max_value_size` on an otherwise small value. The upper bound is
+        // unknown for the same reason this strategy exists: under the fuzz
+        // RNG it keeps consuming elements until the buffer runs out, so
+        // there is no finite cap on how many it can produce.
+        size_hint::unknown()
+    }
+}
+
+impl<T: ValueTree> ValueTree for VecTakeRestValueTree<T> {
+    type Value = Vec<T::Value>;
+
+    fn current(&self) -> Self::Value {
+        self.elements.iter().map(ValueTree::current).collect()
+    }
+
+    fn simplify(&mut self) -> bool {
+        match self.phase {
+            ShrinkPhase::DeleteTail => {
+                if let Some(last) = self.elements.pop() {
+                    self.removed.push(last);
+                    true
+                } else {
+                    self.phase = ShrinkPhase::ShrinkElements(0);
+                    self.simplify()
+                }
+            }
+            ShrinkPhase::ShrinkElements(ref mut i) => {
+                while *i < self.elements.len() {
+                    if self.elements[*i].simplify() {
+                        return true;
+                    }
+                    *i += 1;
+                }
+                false
+            }
+        }
+    }
+
+    fn complicate(&mut self) -> bool {
+        match self.phase {
+            ShrinkPhase::DeleteTail => {
+                if let Some(last) = self.removed.pop() {
+                    self.elements.push(last);
+                    // Once one deletion has been backed off, the test no
+                    // longer fails with it gone, so there is no point
+                    // trying to delete more; shrink what remains instead.
+                    self.phase = ShrinkPhase::ShrinkElements(0);
+                    true
+                } else {
+                    false
+                }
+            }
+            ShrinkPhase::ShrinkElements(i) => {
+                i < self.elements.len() && self.elements[i].complicate()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// A `ValueTree` that shrinks by stepping its value down by one per
+    /// `simplify` and back up by one per `complicate`, for exercising the
+    /// outer `DeleteTail`/`ShrinkElements` state machine without depending
+    /// on a real element strategy's own shrink semantics.
+    #[derive(Clone, Debug)]
+    struct StepDown(u8);
+
+    impl ValueTree for StepDown {
+        type Value = u8;
+
+        fn current(&self) -> u8 {
+            self.0
+        }
+
+        fn simplify(&mut self) -> bool {
+            if self.0 > 0 {
+                self.0 -= 1;
+                true
+            } else {
+                false
+            }
+        }
+
+        fn complicate(&mut self) -> bool {
+            if self.0 < u8::MAX {
+                self.0 += 1;
+                true
+            } else {
+                false
+            }
+        }
+    }
+
+    fn take_rest_tree(lens: &[u8]) -> VecTakeRestValueTree<StepDown> {
+        VecTakeRestValueTree {
+            elements: lens.iter().copied().map(StepDown).collect(),
+            removed: Vec::new(),
+            phase: ShrinkPhase::DeleteTail,
+        }
+    }
+
+    #[test]
+    fn delete_tail_converges_to_minimal_failing_prefix() {
+        // Fails while the vec has at least 3 elements: tail deletion
+        // should stop right at that boundary, the minimal failing prefix.
+        fn fails(v: &[u8]) -> bool {
+            v.len() >= 3
+        }
+
+        let mut tree = take_rest_tree(&[5, 5, 5, 5, 5]);
+        assert!(fails(&tree.current()));
+
+        loop {
+            assert!(tree.simplify(), "ran out of tail to delete before failing");
+            if !fails(&tree.current()) {
+                break;
+            }
+        }
+
+        // One deletion too many: back off by exactly one element.
+        assert!(tree.complicate());
+        assert_eq!(3, tree.elements.len());
+        assert!(fails(&tree.current()));
+        assert!(matches!(tree.phase, ShrinkPhase::ShrinkElements(0)));
+    }
+
+    #[test]
+    fn complicate_restores_exactly_one_element_then_switches_phase() {
+        let mut tree = take_rest_tree(&[1, 2, 3]);
+
+        assert!(tree.simplify());
+        assert_eq!(2, tree.elements.len());
+        assert!(matches!(tree.phase, ShrinkPhase::DeleteTail));
+
+        assert!(tree.complicate());
+        assert_eq!(3, tree.elements.len());
+        assert!(matches!(tree.phase, ShrinkPhase::ShrinkElements(0)));
+
+        // Once switched, further complication delegates to the elements
+        // instead of restoring more deleted tail entries.
+        assert!(!tree.complicate());
+    }
+
+    #[test]
+    fn shrink_elements_phase_delegates_to_each_element() {
+        let mut tree = take_rest_tree(&[2, 0]);
+        tree.phase = ShrinkPhase::ShrinkElements(0);
+
+        assert!(tree.simplify());
+        assert_eq!(vec![1, 0], tree.current());
+        assert!(tree.simplify());
+        assert_eq!(vec![0, 0], tree.current());
+        // The first element is now fully simplified; the second already
+        // was, so there is nothing left to simplify.
+        assert!(!tree.simplify());
+    }
+
+    /// An element strategy that consumes no bytes off the fuzz buffer, the
+    /// way `Just` or `()` would, so that generation can only ever terminate
+    /// by detecting no progress rather than by the buffer reporting
+    /// exhausted.
+    #[derive(Clone, Copy, Debug)]
+    struct ZeroSizeElement;
+
+    impl Strategy for ZeroSizeElement {
+        type Tree = StepDown;
+        type Value = u8;
+
+        fn new_tree(&self, _runner: &mut TestRunner) -> NewTree<Self> {
+            Ok(StepDown(0))
+        }
+
+        fn size_hint(&self) -> SizeHint {
+            size_hint::exact(0)
+        }
+    }
+
+    #[test]
+    fn zero_size_element_terminates_instead_of_looping_forever() {
+        let strategy = vec_take_rest(ZeroSizeElement, 0..1);
+        let mut runner = TestRunner::new_with_rng(
+            Config::default(),
+            TestRng::from_seed(RngAlgorithm::PassThrough, &[1, 2, 3, 4, 5]),
+        );
+
+        let tree = strategy.new_tree(&mut runner).unwrap();
+        assert_eq!(1, tree.current().len());
+    }
+}
@@ -0,0 +1,379 @@
+//-
+// Copyright 2017, 2018 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Strategies for generating primitive integer values.
+//!
+//! All the strategies in this module shrink by binary search towards 0 (or
+//! towards whichever bound of a `Range`/`RangeInclusive` is closest to 0),
+//! and their `size_hint` is exact: a leaf integer always consumes
+//! `mem::size_of::<$t>()` bytes of the `max_value_size` budget, regardless
+//! of which value was drawn.
+
+use core::mem;
+use core::ops::{Range, RangeInclusive};
+
+use rand::Rng;
+
+use crate::strategy::size_hint::{self, SizeHint};
+use crate::strategy::{NewTree, Strategy, ValueTree};
+use crate::test_runner::TestRunner;
+
+macro_rules! unsigned_integer_mod {
+    ($typ:ident) => {
+        /// Strategies for generating `$typ` values.
+        pub mod $typ {
+            use super::*;
+
+            /// `ValueTree` that shrinks a `$typ` towards 0 by binary search.
+            #[derive(Clone, Copy, Debug)]
+            pub struct BinarySearch {
+                lo: u128,
+                cur: u128,
+                hi: u128,
+            }
+
+            impl BinarySearch {
+                fn new(start: $typ) -> Self {
+                    BinarySearch {
+                        lo: 0,
+                        cur: start as u128,
+                        hi: start as u128,
+                    }
+                }
+
+                fn reposition(&mut self) -> bool {
+                    let new_mid = self.lo + (self.hi - self.lo) / 2;
+                    if new_mid == self.cur {
+                        false
+                    } else {
+                        self.cur = new_mid;
+                        true
+                    }
+                }
+            }
+
+            impl ValueTree for BinarySearch {
+                type Value = $typ;
+
+                fn current(&self) -> $typ {
+                    self.cur as $typ
+                }
+
+                fn simplify(&mut self) -> bool {
+                    // No room left between `lo` (the lowest value not yet
+                    // ruled out) and `cur`: nothing more to shrink.
+                    if self.lo == self.cur {
+                        false
+                    } else {
+                        self.hi = self.cur;
+                        self.reposition()
+                    }
+                }
+
+                fn complicate(&mut self) -> bool {
+                    // `cur` is already back at `hi`, the value before the
+                    // last `simplify`: nothing more to undo.
+                    if self.hi == self.cur {
+                        false
+                    } else {
+                        // Nothing at or below `cur` needs trying again.
+                        self.lo = self.cur + 1;
+                        self.cur = self.hi;
+                        true
+                    }
+                }
+            }
+
+            /// Type of the [`ANY`] constant.
+            #[derive(Clone, Copy, Debug)]
+            #[must_use = "strategies do nothing unless used"]
+            pub struct Any(());
+
+            /// Generates `$typ` values, uniformly distributed over the
+            /// whole range.
+            pub const ANY: Any = Any(());
+
+            impl Strategy for Any {
+                type Tree = BinarySearch;
+                type Value = $typ;
+
+                fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+                    Ok(BinarySearch::new(runner.rng().gen()))
+                }
+
+                fn size_hint(&self) -> SizeHint {
+                    size_hint::exact(mem::size_of::<$typ>())
+                }
+            }
+
+            impl Strategy for Range<$typ> {
+                type Tree = BinarySearch;
+                type Value = $typ;
+
+                fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+                    Ok(BinarySearch::new(runner.rng().gen_range(self.clone())))
+                }
+
+                fn size_hint(&self) -> SizeHint {
+                    size_hint::exact(mem::size_of::<$typ>())
+                }
+            }
+
+            impl Strategy for RangeInclusive<$typ> {
+                type Tree = BinarySearch;
+                type Value = $typ;
+
+                fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+                    Ok(BinarySearch::new(runner.rng().gen_range(self.clone())))
+                }
+
+                fn size_hint(&self) -> SizeHint {
+                    size_hint::exact(mem::size_of::<$typ>())
+                }
+            }
+        }
+    };
+}
+
+macro_rules! signed_integer_mod {
+    ($typ:ident) => {
+        /// Strategies for generating `$typ` values.
+        pub mod $typ {
+            use super::*;
+
+            /// `ValueTree` that shrinks a `$typ` towards 0 by binary search.
+            ///
+            /// `lo` and `hi` always bracket `cur` (`lo <= cur <= hi`), with
+            /// whichever one sits on the zero side of the initial value
+            /// doubling as the shrink target: `target_is_lo` records which,
+            /// since for a negative start it's `hi` (starting at 0) that plays
+            /// the role `lo` (starting at 0) plays for a non-negative start.
+            #[derive(Clone, Copy, Debug)]
+            pub struct BinarySearch {
+                lo: i128,
+                cur: i128,
+                hi: i128,
+                target_is_lo: bool,
+            }
+
+            impl BinarySearch {
+                fn new(start: $typ) -> Self {
+                    let start = start as i128;
+                    if start >= 0 {
+                        BinarySearch {
+                            lo: 0,
+                            cur: start,
+                            hi: start,
+                            target_is_lo: true,
+                        }
+                    } else {
+                        BinarySearch {
+                            lo: start,
+                            cur: start,
+                            hi: 0,
+                            target_is_lo: false,
+                        }
+                    }
+                }
+
+                fn reposition(&mut self) -> bool {
+                    // Round towards the shrink target, so that `cur` can
+                    // reach it from either side.
+                    let half = (self.hi - self.lo) / 2;
+                    let new_mid = if self.target_is_lo {
+                        self.lo + half
+                    } else {
+                        self.hi - half
+                    };
+                    if new_mid == self.cur {
+                        false
+                    } else {
+                        self.cur = new_mid;
+                        true
+                    }
+                }
+            }
+
+            impl ValueTree for BinarySearch {
+                type Value = $typ;
+
+                fn current(&self) -> $typ {
+                    self.cur as $typ
+                }
+
+                fn simplify(&mut self) -> bool {
+                    if self.target_is_lo {
+                        // No room left between `lo` (0) and `cur`.
+                        if self.lo == self.cur {
+                            false
+                        } else {
+                            self.hi = self.cur;
+                            self.reposition()
+                        }
+                    } else {
+                        // No room left between `cur` and `hi` (0).
+                        if self.hi == self.cur {
+                            false
+                        } else {
+                            self.lo = self.cur;
+                            self.reposition()
+                        }
+                    }
+                }
+
+                fn complicate(&mut self) -> bool {
+                    // Return to the value before the last `simplify`, ruling
+                    // out everything from `cur` towards the shrink target.
+                    if self.target_is_lo {
+                        if self.hi == self.cur {
+                            false
+                        } else {
+                            self.lo = self.cur + 1;
+                            self.cur = self.hi;
+                            true
+                        }
+                    } else {
+                        if self.lo == self.cur {
+                            false
+                        } else {
+                            self.hi = self.cur - 1;
+                            self.cur = self.lo;
+                            true
+                        }
+                    }
+                }
+            }
+
+            /// Type of the [`ANY`] constant.
+            #[derive(Clone, Copy, Debug)]
+            #[must_use = "strategies do nothing unless used"]
+            pub struct Any(());
+
+            /// Generates `$typ` values, uniformly distributed over the
+            /// whole range.
+            pub const ANY: Any = Any(());
+
+            impl Strategy for Any {
+                type Tree = BinarySearch;
+                type Value = $typ;
+
+                fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+                    Ok(BinarySearch::new(runner.rng().gen()))
+                }
+
+                fn size_hint(&self) -> SizeHint {
+                    size_hint::exact(mem::size_of::<$typ>())
+                }
+            }
+
+            impl Strategy for Range<$typ> {
+                type Tree = BinarySearch;
+                type Value = $typ;
+
+                fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+                    Ok(BinarySearch::new(runner.rng().gen_range(self.clone())))
+                }
+
+                fn size_hint(&self) -> SizeHint {
+                    size_hint::exact(mem::size_of::<$typ>())
+                }
+            }
+
+            impl Strategy for RangeInclusive<$typ> {
+                type Tree = BinarySearch;
+                type Value = $typ;
+
+                fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+                    Ok(BinarySearch::new(runner.rng().gen_range(self.clone())))
+                }
+
+                fn size_hint(&self) -> SizeHint {
+                    size_hint::exact(mem::size_of::<$typ>())
+                }
+            }
+        }
+    };
+}
+
+unsigned_integer_mod!(u8);
+unsigned_integer_mod!(u16);
+unsigned_integer_mod!(u32);
+unsigned_integer_mod!(u64);
+unsigned_integer_mod!(u128);
+unsigned_integer_mod!(usize);
+signed_integer_mod!(i8);
+signed_integer_mod!(i16);
+signed_integer_mod!(i32);
+signed_integer_mod!(i64);
+signed_integer_mod!(i128);
+signed_integer_mod!(isize);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_runner::Config;
+
+    #[test]
+    fn any_leaf_reports_its_byte_width_as_an_exact_size_hint() {
+        assert_eq!(size_hint::exact(8), u64::ANY.size_hint());
+        assert_eq!(size_hint::exact(1), u8::ANY.size_hint());
+        assert_eq!(size_hint::exact(4), i32::ANY.size_hint());
+    }
+
+    #[test]
+    fn unsigned_shrinks_towards_zero() {
+        let mut runner = TestRunner::new(Config::default());
+        let mut tree = (0u32..1000).new_tree(&mut runner).unwrap();
+        while tree.simplify() {}
+        assert_eq!(0, tree.current());
+    }
+
+    #[test]
+    fn signed_shrinks_towards_zero_from_either_side() {
+        let mut runner = TestRunner::new(Config::default());
+
+        let mut positive = 0;
+        let mut negative = 0;
+        // `(-1000..1000)` can draw either a non-negative or a negative
+        // start value; run it enough times to exercise both shrink
+        // directions.
+        for _ in 0..64 {
+            let mut tree = (-1000i32..1000).new_tree(&mut runner).unwrap();
+            let started_negative = tree.current() < 0;
+            while tree.simplify() {}
+            assert_eq!(0, tree.current());
+            if started_negative {
+                negative += 1;
+            } else {
+                positive += 1;
+            }
+        }
+        assert!(positive > 0, "never drew a non-negative start value");
+        assert!(negative > 0, "never drew a negative start value");
+    }
+
+    #[test]
+    fn simplify_then_complicate_restores_the_original_value() {
+        let mut runner = TestRunner::new(Config::default());
+        for _ in 0..64 {
+            let mut tree = (-1000i32..1000).new_tree(&mut runner).unwrap();
+            let initial = tree.current();
+
+            let mut steps = 0;
+            while tree.simplify() {
+                steps += 1;
+            }
+            for _ in 0..steps {
+                assert!(tree.complicate());
+            }
+
+            assert_eq!(initial, tree.current());
+        }
+    }
+}
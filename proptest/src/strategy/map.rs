@@ -0,0 +1,85 @@
+//-
+// Copyright 2024 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use core::fmt;
+
+use crate::test_runner::TestRunner;
+
+use super::size_hint::SizeHint;
+use super::traits::{NewTree, Strategy, ValueTree};
+
+/// `Strategy` returned by `Strategy::prop_map`.
+#[derive(Clone, Copy)]
+pub struct Map<S, F> {
+    source: S,
+    fun: F,
+}
+
+impl<S, F> Map<S, F> {
+    pub(crate) fn new(source: S, fun: F) -> Self {
+        Map { source, fun }
+    }
+}
+
+impl<S: fmt::Debug, F> fmt::Debug for Map<S, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Map")
+            .field("source", &self.source)
+            .field("fun", &"<function>")
+            .finish()
+    }
+}
+
+impl<S: Strategy, O: fmt::Debug, F: Fn(S::Value) -> O + Clone> Strategy for Map<S, F> {
+    type Tree = MapValueTree<S::Tree, F>;
+    type Value = O;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        Ok(MapValueTree {
+            source: self.source.new_tree(runner)?,
+            fun: self.fun.clone(),
+        })
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.source.size_hint()
+    }
+}
+
+/// `ValueTree` returned by `Map`'s `Strategy::new_tree`.
+#[derive(Clone, Copy)]
+pub struct MapValueTree<T, F> {
+    source: T,
+    fun: F,
+}
+
+impl<T: fmt::Debug, F> fmt::Debug for MapValueTree<T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MapValueTree")
+            .field("source", &self.source)
+            .field("fun", &"<function>")
+            .finish()
+    }
+}
+
+impl<T: ValueTree, O: fmt::Debug, F: Fn(T::Value) -> O + Clone> ValueTree for MapValueTree<T, F> {
+    type Value = O;
+
+    fn current(&self) -> O {
+        (self.fun)(self.source.current())
+    }
+
+    fn simplify(&mut self) -> bool {
+        self.source.simplify()
+    }
+
+    fn complicate(&mut self) -> bool {
+        self.source.complicate()
+    }
+}
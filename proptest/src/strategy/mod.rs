@@ -0,0 +1,20 @@
+//-
+// Copyright 2024 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Definitions of the core `Strategy` and `ValueTree` traits.
+
+mod map;
+mod traits;
+mod union;
+
+pub mod size_hint;
+
+pub use self::map::{Map, MapValueTree};
+pub use self::traits::{BoxedStrategy, NewTree, Strategy, ValueTree};
+pub use self::union::Union;
@@ -0,0 +1,54 @@
+//-
+// Copyright 2024 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Helpers for computing [`Strategy::size_hint`](super::Strategy::size_hint),
+//! mirroring the `size_hint` module of the `arbitrary` crate.
+
+/// A `(lower, upper)` estimate of how much "size" a strategy's values
+/// consume, in the same unit as [`TestRunner`](crate::test_runner::TestRunner)'s
+/// `max_value_size` budget. `upper` is `None` when no finite bound is known,
+/// e.g. because the strategy recurses or reads an unbounded amount of input.
+pub type SizeHint = (usize, Option<usize>);
+
+/// The hint for a strategy whose size cannot be bounded, which is also the
+/// default returned by [`Strategy::size_hint`](super::Strategy::size_hint).
+pub const fn unknown() -> SizeHint {
+    (0, None)
+}
+
+/// The hint for a strategy that always produces a value of the given fixed
+/// size, such as a primitive integer.
+pub const fn exact(size: usize) -> SizeHint {
+    (size, Some(size))
+}
+
+/// Combine the hints of two strategies whose output is concatenated, e.g.
+/// the fields of a tuple or struct: lower bounds and upper bounds are summed.
+pub fn and(a: SizeHint, b: SizeHint) -> SizeHint {
+    let lower = a.0.saturating_add(b.0);
+    let upper = a.1.zip(b.1).and_then(|(a, b)| a.checked_add(b));
+    (lower, upper)
+}
+
+/// Combine the hints of two strategies of which only one will be used, e.g.
+/// the arms of a union: the smaller lower bound and the larger upper bound
+/// win.
+pub fn or(a: SizeHint, b: SizeHint) -> SizeHint {
+    let lower = a.0.min(b.0);
+    let upper = a.1.zip(b.1).map(|(a, b)| a.max(b));
+    (lower, upper)
+}
+
+/// The hint for a fixed-size sequence of `count` values each drawn according
+/// to `element`, such as a fixed-length array.
+pub fn and_all_n(element: SizeHint, count: usize) -> SizeHint {
+    let lower = element.0.saturating_mul(count);
+    let upper = element.1.and_then(|u| u.checked_mul(count));
+    (lower, upper)
+}
@@ -0,0 +1,145 @@
+//-
+// Copyright 2024 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use core::fmt;
+
+use crate::std_facade::Box;
+use crate::test_runner::{Reason, TestRunner};
+
+use super::map::Map;
+use super::size_hint::{self, SizeHint};
+
+/// A strategy for producing arbitrary values of a given type.
+///
+/// `Strategy` is the core trait of proptest: everything that can appear on
+/// the right-hand side of `x in ...` in a `proptest!` block implements it.
+pub trait Strategy: fmt::Debug {
+    /// The value tree generated by this `Strategy`.
+    type Tree: ValueTree<Value = Self::Value>;
+    /// The type of value used by functions under test generated by this
+    /// `Strategy`.
+    type Value: fmt::Debug;
+
+    /// Generate a new value tree from the given runner.
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self>;
+
+    /// Returns a bound on how much "size" (in the sense of
+    /// [`TestRunner`]'s `max_value_size` budget) values produced by this
+    /// strategy consume.
+    ///
+    /// The default conservatively reports no known bound; strategies that
+    /// can pathologically blow up (collections, recursive strategies) should
+    /// override this to combine their children's hints via the helpers in
+    /// [`size_hint`](super::size_hint), so that consumers generating from
+    /// untrusted input (e.g. a fuzzer corpus) can refuse or truncate before
+    /// allocating.
+    fn size_hint(&self) -> SizeHint {
+        size_hint::unknown()
+    }
+
+    /// Returns a strategy which produces values transformed by `fun`.
+    fn prop_map<O: fmt::Debug, F: Fn(Self::Value) -> O + Clone>(self, fun: F) -> Map<Self, F>
+    where
+        Self: Sized,
+    {
+        Map::new(self, fun)
+    }
+
+    /// Erases the type of this `Strategy` so it can be used as a
+    /// `BoxedStrategy<Self::Value>`, e.g. to put strategies of differing
+    /// concrete types into the same collection, such as the arms of a
+    /// weighted [`Union`](super::union::Union).
+    fn boxed(self) -> BoxedStrategy<Self::Value>
+    where
+        Self: Sized + 'static,
+    {
+        Box::new(BoxedStrategyWrapper(self))
+    }
+}
+
+/// A generated value and the means to shrink it further if it turns out to
+/// provoke a test failure.
+pub trait ValueTree {
+    /// The type of the value produced by this `ValueTree`.
+    type Value: fmt::Debug;
+
+    /// Returns the current value.
+    fn current(&self) -> Self::Value;
+
+    /// Attempts to simplify the current value. Returns whether the value
+    /// changed.
+    fn simplify(&mut self) -> bool;
+
+    /// Undoes the last `simplify`, making this value tree point to the
+    /// complicated value before that call. Returns whether the value
+    /// changed.
+    fn complicate(&mut self) -> bool;
+}
+
+/// Alias for the `Result` returned by `Strategy::new_tree`.
+pub type NewTree<S> = Result<<S as Strategy>::Tree, Reason>;
+
+/// A `Strategy` whose concrete type has been erased behind a trait object,
+/// so that strategies of differing underlying types but the same `Value`
+/// can be used interchangeably, e.g. as weighted arms of a [`Union`].
+///
+/// [`Union`]: super::union::Union
+pub type BoxedStrategy<T> = Box<dyn Strategy<Value = T, Tree = Box<dyn ValueTree<Value = T>>>>;
+
+struct BoxedStrategyWrapper<S>(S);
+
+impl<S: fmt::Debug> fmt::Debug for BoxedStrategyWrapper<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl<S: Strategy + 'static> Strategy for BoxedStrategyWrapper<S> {
+    type Tree = Box<dyn ValueTree<Value = S::Value>>;
+    type Value = S::Value;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        Ok(Box::new(self.0.new_tree(runner)?))
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.0.size_hint()
+    }
+}
+
+impl<T: fmt::Debug> Strategy
+    for Box<dyn Strategy<Value = T, Tree = Box<dyn ValueTree<Value = T>>>>
+{
+    type Tree = Box<dyn ValueTree<Value = T>>;
+    type Value = T;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        (**self).new_tree(runner)
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        (**self).size_hint()
+    }
+}
+
+impl<T: fmt::Debug> ValueTree for Box<dyn ValueTree<Value = T>> {
+    type Value = T;
+
+    fn current(&self) -> T {
+        (**self).current()
+    }
+
+    fn simplify(&mut self) -> bool {
+        (**self).simplify()
+    }
+
+    fn complicate(&mut self) -> bool {
+        (**self).complicate()
+    }
+}
@@ -0,0 +1,66 @@
+//-
+// Copyright 2024 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::std_facade::vec::Vec;
+
+use super::size_hint::{self, SizeHint};
+use super::traits::{NewTree, Strategy, ValueTree};
+use crate::test_runner::TestRunner;
+
+/// A `Strategy` that picks one of several weighted `Strategy`s and defers to
+/// it entirely, including for shrinking.
+///
+/// All arms must produce the same `Value`/`Tree` types; use
+/// [`Strategy::boxed`] on each arm first if they are otherwise different
+/// concrete types.
+#[derive(Debug)]
+pub struct Union<T> {
+    arms: Vec<(u32, T)>,
+}
+
+impl<T: Strategy> Union<T> {
+    /// Create a `Union` picking among `arms`, each weighted by the `u32`
+    /// paired with it. A weight of `0` disables an arm entirely: it is kept
+    /// rather than rejected, but is never picked.
+    pub fn new(arms: impl IntoIterator<Item = (u32, T)>) -> Self {
+        let arms: Vec<_> = arms.into_iter().collect();
+        assert!(
+            arms.iter().any(|&(weight, _)| weight > 0),
+            "Union requires at least one arm with non-zero weight"
+        );
+        Union { arms }
+    }
+}
+
+impl<T: Strategy> Strategy for Union<T> {
+    type Tree = T::Tree;
+    type Value = T::Value;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        let total: u64 = self.arms.iter().map(|&(weight, _)| u64::from(weight)).sum();
+        let mut choice = runner.rng().choose(total);
+
+        for (weight, arm) in &self.arms {
+            if choice < u64::from(*weight) {
+                return arm.new_tree(runner);
+            }
+            choice -= u64::from(*weight);
+        }
+
+        unreachable!("choice must land within the total arm weight")
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.arms
+            .iter()
+            .map(|(_, arm)| arm.size_hint())
+            .reduce(size_hint::or)
+            .unwrap_or_else(size_hint::unknown)
+    }
+}
@@ -0,0 +1,37 @@
+//-
+// Copyright 2024 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+/// Configuration for how a proptest test should be run.
+#[derive(Clone, Debug)]
+pub struct Config {
+    /// The number of successful test cases that must execute for the test
+    /// as a whole to pass.
+    pub cases: u32,
+
+    /// An upper bound, in the same unit as `Strategy::size_hint`, on how
+    /// large a single generated value may be.
+    ///
+    /// Strategies that report a `size_hint` whose lower bound already
+    /// exceeds this value should refuse to generate rather than attempt to
+    /// allocate; this matters most when generation is driven by untrusted
+    /// input, such as raw fuzzer bytes, where nothing else would otherwise
+    /// stop a malicious input from requesting a gigabyte-sized `Vec`.
+    ///
+    /// `None` means no limit is enforced.
+    pub max_value_size: Option<usize>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            cases: 256,
+            max_value_size: None,
+        }
+    }
+}
@@ -0,0 +1,108 @@
+//-
+// Copyright 2024 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use core::fmt;
+
+use crate::std_facade::String;
+
+/// A message explaining why a particular value generation attempt was
+/// rejected or otherwise could not proceed, e.g. from `prop_filter` or a
+/// `Strategy` that refuses to produce a value too large for the configured
+/// `max_value_size`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Reason(&'static str);
+
+impl Reason {
+    /// Wrap a static message as a `Reason`.
+    pub const fn from_static(message: &'static str) -> Self {
+        Reason(message)
+    }
+
+    /// The message describing the reason.
+    pub fn message(&self) -> &str {
+        self.0
+    }
+}
+
+impl From<&'static str> for Reason {
+    fn from(message: &'static str) -> Self {
+        Reason::from_static(message)
+    }
+}
+
+impl fmt::Display for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+/// The error type for a single invocation of a test function passed to
+/// [`TestRunner::run`](super::TestRunner::run).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TestCaseError {
+    /// The current case should be rejected and a fresh one generated in its
+    /// place, e.g. because a `prop_assume!` precondition wasn't met. Does
+    /// not count as a failure.
+    Reject(String),
+    /// The current case failed the test outright.
+    Fail(String),
+}
+
+impl TestCaseError {
+    /// Rejects the current test case, e.g. because some precondition on the
+    /// input wasn't met.
+    pub fn reject(reason: impl Into<String>) -> Self {
+        TestCaseError::Reject(reason.into())
+    }
+
+    /// Fails the current test case with the given message.
+    pub fn fail(reason: impl Into<String>) -> Self {
+        TestCaseError::Fail(reason.into())
+    }
+}
+
+impl fmt::Display for TestCaseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TestCaseError::Reject(reason) => write!(f, "Test case rejected: {}", reason),
+            TestCaseError::Fail(reason) => write!(f, "Test case failed: {}", reason),
+        }
+    }
+}
+
+/// The `Result` type returned by test functions passed to
+/// [`TestRunner::run`](super::TestRunner::run).
+pub type TestCaseResult = Result<(), TestCaseError>;
+
+/// The error type returned by [`TestRunner::run`](super::TestRunner::run)
+/// when it is unable to complete successfully.
+#[derive(Clone, Debug)]
+pub enum TestError<V> {
+    /// The run stopped before `cases` test cases could pass, e.g. because
+    /// the strategy could not produce a value within the configured
+    /// `max_value_size`, or too many generated cases were rejected over the
+    /// whole run.
+    Abort(Reason),
+    /// A generated case failed; carries the minimal failing input found by
+    /// shrinking and a message describing the failure.
+    Fail(String, V),
+}
+
+impl<V: fmt::Debug> fmt::Display for TestError<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TestError::Abort(reason) => write!(f, "Test aborted: {}", reason),
+            TestError::Fail(reason, value) => write!(
+                f,
+                "Test failed: {}\n\tminimal failing input: {:?}",
+                reason, value
+            ),
+        }
+    }
+}
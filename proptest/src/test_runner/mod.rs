@@ -0,0 +1,25 @@
+//-
+// Copyright 2024 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! State and functions for running proptest tests.
+//!
+//! You do not normally need to access things in this module directly except
+//! when implementing new strategies.
+
+mod config;
+mod errors;
+mod rng;
+mod runner;
+mod sanity;
+
+pub use self::config::Config;
+pub use self::errors::{Reason, TestCaseError, TestCaseResult, TestError};
+pub use self::rng::{RngAlgorithm, TestRng};
+pub use self::runner::TestRunner;
+pub use self::sanity::{check_strategy_sanity, CheckStrategySanityOptions};
@@ -0,0 +1,277 @@
+//-
+// Copyright 2024 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The random number generator used to drive value generation.
+
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaChaRng;
+use rand_xorshift::XorShiftRng;
+
+/// Identifies a particular source of randomness that a [`TestRng`] can be
+/// constructed from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RngAlgorithm {
+    /// An `XorShiftRng` seeded by the given bytes.
+    XorShift,
+    /// A `ChaChaRng` seeded by the given bytes.
+    ChaCha,
+    /// Not a PRNG at all: the given bytes are used directly as the source of
+    /// "randomness", consumed in order off the front of the buffer.
+    ///
+    /// Each request for `n` bytes of randomness takes the next `n` bytes off
+    /// the front of the buffer; once the buffer is exhausted, all further
+    /// requests are answered with zero bytes. This never errors and never
+    /// blocks, so generation under this mode always terminates, making it
+    /// suitable for driving strategies from a libFuzzer/`cargo-fuzz` corpus
+    /// entry.
+    PassThrough,
+}
+
+enum TestRngImpl {
+    XorShift(XorShiftRng),
+    ChaCha(ChaChaRng),
+    PassThrough { data: Vec<u8>, off: usize },
+}
+
+/// Random number generator used by proptest to generate and shrink test
+/// cases.
+///
+/// This is a thin wrapper around one of a handful of interchangeable
+/// randomness sources selected by [`RngAlgorithm`]; most users never need to
+/// construct one directly, aside from seeding fuzz-driven runs via
+/// [`TestRng::from_seed`] with [`RngAlgorithm::PassThrough`].
+pub struct TestRng {
+    rng: TestRngImpl,
+}
+
+impl TestRng {
+    /// Create a new `TestRng` using the given algorithm, seeded with `seed`.
+    ///
+    /// For [`RngAlgorithm::PassThrough`], `seed` is the raw byte buffer that
+    /// generation will consume from directly, rather than a PRNG seed.
+    pub fn from_seed(algorithm: RngAlgorithm, seed: &[u8]) -> Self {
+        let rng = match algorithm {
+            RngAlgorithm::XorShift => TestRngImpl::XorShift(seed_rng(seed)),
+            RngAlgorithm::ChaCha => TestRngImpl::ChaCha(seed_rng(seed)),
+            RngAlgorithm::PassThrough => TestRngImpl::PassThrough {
+                data: seed.to_vec(),
+                off: 0,
+            },
+        };
+
+        TestRng { rng }
+    }
+
+    /// Create a `TestRng` driven directly by `bytes`, for evaluating a
+    /// strategy once against fuzzer-supplied input.
+    ///
+    /// Equivalent to `TestRng::from_seed(RngAlgorithm::PassThrough, bytes)`.
+    pub fn from_fuzz_bytes(bytes: &[u8]) -> Self {
+        Self::from_seed(RngAlgorithm::PassThrough, bytes)
+    }
+
+    /// Create a `TestRng` seeded from OS entropy, using the default PRNG
+    /// algorithm.
+    pub(crate) fn default_rng() -> Self {
+        TestRng {
+            rng: TestRngImpl::XorShift(XorShiftRng::from_entropy()),
+        }
+    }
+
+    /// Reads `dest.len()` bytes off the front of `data` starting at `*off`,
+    /// zero-filling whatever is left once `data` is exhausted, mirroring
+    /// `arbitrary`'s `Unstructured::fill_buffer`.
+    fn fill_from_buffer(data: &[u8], off: &mut usize, dest: &mut [u8]) {
+        let available = data.len().saturating_sub(*off);
+        let taken = available.min(dest.len());
+        dest[..taken].copy_from_slice(&data[*off..*off + taken]);
+        for b in &mut dest[taken..] {
+            *b = 0;
+        }
+        *off += taken;
+    }
+
+    /// Returns whether this `TestRng` sources its randomness directly from a
+    /// byte buffer (i.e. was constructed via [`RngAlgorithm::PassThrough`]),
+    /// as opposed to a PRNG.
+    pub fn is_byte_driven(&self) -> bool {
+        matches!(self.rng, TestRngImpl::PassThrough { .. })
+    }
+
+    /// For a byte-driven `TestRng`, returns whether its buffer has been
+    /// fully consumed. Always `false` for PRNG-backed sources, which never
+    /// run out.
+    pub fn fuzz_bytes_exhausted(&self) -> bool {
+        match &self.rng {
+            TestRngImpl::PassThrough { data, off } => *off >= data.len(),
+            _ => false,
+        }
+    }
+
+    /// For a byte-driven `TestRng`, returns how many bytes of its buffer are
+    /// left unconsumed. `None` for PRNG-backed sources, which have no
+    /// buffer to exhaust.
+    ///
+    /// Callers that keep drawing from a strategy until this stops changing
+    /// can detect a zero-size element (one that consumes no bytes) and stop,
+    /// rather than looping forever without the buffer ever reporting
+    /// exhausted.
+    pub fn fuzz_bytes_remaining(&self) -> Option<usize> {
+        match &self.rng {
+            TestRngImpl::PassThrough { data, off } => Some(data.len().saturating_sub(*off)),
+            _ => None,
+        }
+    }
+
+    /// Consumes just enough bytes to cover `bound` possibilities and reduces
+    /// them modulo `bound`, mirroring `arbitrary`'s `int_in_range`.
+    ///
+    /// Used by bounded choices such as enum variant selection or collection
+    /// length, so that a handful of fuzzer bytes can steer a decision
+    /// without requiring a full `u64` read per choice.
+    pub(crate) fn choose(&mut self, bound: u64) -> u64 {
+        if bound == 0 {
+            return 0;
+        }
+
+        match &mut self.rng {
+            TestRngImpl::PassThrough { data, off } => {
+                let bytes_needed = ((64 - bound.leading_zeros()) as usize + 7) / 8;
+                let bytes_needed = bytes_needed.max(1);
+                let mut buf = [0u8; 8];
+                Self::fill_from_buffer(data, off, &mut buf[..bytes_needed]);
+                u64::from_le_bytes(buf) % bound
+            }
+            _ => self.next_u64() % bound,
+        }
+    }
+}
+
+impl RngCore for TestRng {
+    fn next_u32(&mut self) -> u32 {
+        match &mut self.rng {
+            TestRngImpl::XorShift(rng) => rng.next_u32(),
+            TestRngImpl::ChaCha(rng) => rng.next_u32(),
+            TestRngImpl::PassThrough { data, off } => {
+                let mut buf = [0u8; 4];
+                Self::fill_from_buffer(data, off, &mut buf);
+                u32::from_le_bytes(buf)
+            }
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        match &mut self.rng {
+            TestRngImpl::XorShift(rng) => rng.next_u64(),
+            TestRngImpl::ChaCha(rng) => rng.next_u64(),
+            TestRngImpl::PassThrough { data, off } => {
+                let mut buf = [0u8; 8];
+                Self::fill_from_buffer(data, off, &mut buf);
+                u64::from_le_bytes(buf)
+            }
+        }
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        match &mut self.rng {
+            TestRngImpl::XorShift(rng) => rng.fill_bytes(dest),
+            TestRngImpl::ChaCha(rng) => rng.fill_bytes(dest),
+            TestRngImpl::PassThrough { data, off } => Self::fill_from_buffer(data, off, dest),
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+fn seed_rng<R: SeedableRng>(seed: &[u8]) -> R {
+    let mut full_seed = R::Seed::default();
+    {
+        let full_seed_ref = full_seed.as_mut();
+        for (i, b) in seed.iter().enumerate() {
+            full_seed_ref[i % full_seed_ref.len()] ^= *b;
+        }
+    }
+    R::from_seed(full_seed)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn fill_bytes_reads_little_endian_off_the_front() {
+        let mut rng = TestRng::from_fuzz_bytes(&[0x01, 0x02, 0x03, 0x04, 0xff]);
+        assert_eq!(0x0403_0201, rng.next_u32());
+        // The next read starts right after the last one left off.
+        assert_eq!(0x0000_00ff, rng.next_u32());
+    }
+
+    #[test]
+    fn exhausted_buffer_zero_fills_instead_of_erroring() {
+        let mut rng = TestRng::from_fuzz_bytes(&[0xab]);
+        assert_eq!(0x0000_00ab, rng.next_u32());
+        assert!(rng.fuzz_bytes_exhausted());
+        // Further reads terminate with zeroes rather than panicking.
+        assert_eq!(0, rng.next_u64());
+        assert_eq!([0u8; 3], {
+            let mut buf = [0u8; 3];
+            rng.fill_bytes(&mut buf);
+            buf
+        });
+    }
+
+    #[test]
+    fn empty_buffer_is_immediately_exhausted() {
+        let mut rng = TestRng::from_fuzz_bytes(&[]);
+        assert!(rng.fuzz_bytes_exhausted());
+        assert_eq!(Some(0), rng.fuzz_bytes_remaining());
+        assert_eq!(0, rng.next_u64());
+    }
+
+    #[test]
+    fn fuzz_bytes_remaining_tracks_consumption() {
+        let mut rng = TestRng::from_fuzz_bytes(&[0, 1, 2, 3, 4, 5]);
+        assert_eq!(Some(6), rng.fuzz_bytes_remaining());
+        let _ = rng.next_u32();
+        assert_eq!(Some(2), rng.fuzz_bytes_remaining());
+        let _ = rng.next_u32();
+        assert_eq!(Some(0), rng.fuzz_bytes_remaining());
+        assert!(rng.fuzz_bytes_exhausted());
+    }
+
+    #[test]
+    fn prng_sources_report_no_fuzz_buffer() {
+        let rng = TestRng::default_rng();
+        assert!(!rng.is_byte_driven());
+        assert!(!rng.fuzz_bytes_exhausted());
+        assert_eq!(None, rng.fuzz_bytes_remaining());
+    }
+
+    #[test]
+    fn choose_is_deterministic_for_a_fixed_buffer() {
+        let mut a = TestRng::from_fuzz_bytes(&[0x10, 0x00, 0x20]);
+        let mut b = TestRng::from_fuzz_bytes(&[0x10, 0x00, 0x20]);
+        assert_eq!(a.choose(5), b.choose(5));
+    }
+
+    #[test]
+    fn choose_reduces_modulo_the_bound() {
+        for bound in [1u64, 2, 5, 17, 1000] {
+            let mut rng = TestRng::from_fuzz_bytes(&[0xff, 0xff, 0xff, 0xff]);
+            assert!(rng.choose(bound) < bound);
+        }
+
+        let mut rng = TestRng::from_fuzz_bytes(&[0xff, 0xff]);
+        assert_eq!(0, rng.choose(0));
+    }
+}
@@ -0,0 +1,199 @@
+//-
+// Copyright 2024 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use core::fmt;
+use std::any::Any;
+use std::boxed::Box;
+use std::panic::{self, AssertUnwindSafe};
+
+use super::config::Config;
+use super::errors::{Reason, TestCaseError, TestCaseResult, TestError};
+use super::rng::{RngAlgorithm, TestRng};
+use crate::strategy::size_hint::SizeHint;
+use crate::strategy::{Strategy, ValueTree};
+
+/// State used when generating and shrinking test cases.
+pub struct TestRunner {
+    config: Config,
+    rng: TestRng,
+}
+
+impl TestRunner {
+    /// Create a fresh `TestRunner` with the given configuration, seeding its
+    /// RNG from entropy.
+    pub fn new(config: Config) -> Self {
+        let rng = TestRng::default_rng();
+        TestRunner { config, rng }
+    }
+
+    /// Create a fresh `TestRunner` with the given configuration, using the
+    /// given random number generator for all value generation.
+    pub fn new_with_rng(config: Config, rng: TestRng) -> Self {
+        TestRunner { config, rng }
+    }
+
+    /// Create a `TestRunner` with the default configuration, seeded
+    /// reproducibly rather than from entropy.
+    ///
+    /// Intended for this crate's own strategy tests, where a flaky failure
+    /// caused by an unlucky seed is worse than a deterministic one that
+    /// reproduces the same way every run.
+    pub fn deterministic() -> Self {
+        TestRunner::new_with_rng(
+            Config::default(),
+            TestRng::from_seed(RngAlgorithm::XorShift, &[0u8; 16]),
+        )
+    }
+
+    /// Create a `TestRunner` whose randomness is sourced directly from
+    /// `bytes` rather than a pseudo-random algorithm.
+    ///
+    /// This is the entry point used to drive `Strategy` generation from
+    /// coverage-guided fuzzer input; see the [`proptest_fuzz_target`] macro
+    /// for the common case of evaluating a single `Arbitrary` type.
+    ///
+    /// [`proptest_fuzz_target`]: ../macro.proptest_fuzz_target.html
+    pub fn from_fuzz_bytes(bytes: &[u8]) -> Self {
+        Self::new_with_rng(
+            Config::default(),
+            TestRng::from_seed(RngAlgorithm::PassThrough, bytes),
+        )
+    }
+
+    /// Returns the RNG for this test run.
+    pub fn rng(&mut self) -> &mut TestRng {
+        &mut self.rng
+    }
+
+    /// Returns the configuration for this test run.
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Checks `hint`'s lower bound against this run's configured
+    /// `max_value_size`, returning a rejection `Reason` if it is exceeded.
+    ///
+    /// Strategies that cannot shrink a value's size after the fact (e.g. a
+    /// fixed-length array) should call this with their own `size_hint()`
+    /// before generating, so that an oversized value is refused outright
+    /// rather than allocated.
+    pub fn check_size_budget(&self, hint: SizeHint) -> Result<(), Reason> {
+        if let Some(max) = self.config.max_value_size {
+            if hint.0 > max {
+                return Err(Reason::from_static(
+                    "value's size_hint lower bound exceeds the configured max_value_size",
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Runs `test` against `self.config().cases` values generated from
+    /// `strategy`.
+    ///
+    /// If a case fails (either by `test` returning `Err(TestCaseError::Fail
+    /// (..))` or by panicking), the failing value is shrunk by repeatedly
+    /// calling `ValueTree::simplify`/`complicate` until neither changes the
+    /// outcome, and the minimal failing value found is reported in the
+    /// returned `TestError`. A case that returns `Err(TestCaseError::Reject
+    /// (..))` doesn't count towards `cases` and is simply replaced by a
+    /// freshly generated one, up to a bounded number of rejections.
+    pub fn run<S: Strategy>(
+        &mut self,
+        strategy: &S,
+        test: impl Fn(&S::Value) -> TestCaseResult,
+    ) -> Result<(), TestError<S::Value>> {
+        let previous_hook = panic::take_hook();
+        panic::set_hook(Box::new(|_| {}));
+        let result = self.run_with_hook_suppressed(strategy, test);
+        panic::set_hook(previous_hook);
+        result
+    }
+
+    fn run_with_hook_suppressed<S: Strategy>(
+        &mut self,
+        strategy: &S,
+        test: impl Fn(&S::Value) -> TestCaseResult,
+    ) -> Result<(), TestError<S::Value>> {
+        const MAX_REJECTS: u32 = 1024;
+
+        let mut rejects = 0u32;
+        let mut cases_run = 0u32;
+
+        while cases_run < self.config.cases {
+            let mut tree = strategy.new_tree(self).map_err(TestError::Abort)?;
+
+            match Self::run_one(&test, &tree.current()) {
+                Ok(()) => cases_run += 1,
+                Err(TestCaseError::Reject(_)) => {
+                    rejects += 1;
+                    if rejects >= MAX_REJECTS {
+                        return Err(TestError::Abort(Reason::from_static(
+                            "too many test cases were rejected; check prop_assume! calls \
+                             and any filters",
+                        )));
+                    }
+                }
+                Err(TestCaseError::Fail(reason)) => {
+                    let value = Self::shrink(&mut tree, &test);
+                    return Err(TestError::Fail(reason, value));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Shrinks `tree` towards a minimal value that still fails `test`,
+    /// mirroring the strategy used by this crate's own hand-written
+    /// shrink loops (see e.g. `array`'s strategy tests): simplify while the
+    /// test keeps failing, and back off with `complicate` the moment it
+    /// starts passing again.
+    fn shrink<T: ValueTree>(
+        tree: &mut T,
+        test: &impl Fn(&T::Value) -> TestCaseResult,
+    ) -> T::Value {
+        loop {
+            if Self::run_one(test, &tree.current()).is_ok() {
+                if !tree.complicate() {
+                    break;
+                }
+            } else if !tree.simplify() {
+                break;
+            }
+        }
+
+        tree.current()
+    }
+
+    /// Runs `test` once against `value`, converting a panic (e.g. from a
+    /// failed `assert!` in a `proptest!` body) into `TestCaseError::Fail`.
+    fn run_one<V>(test: &impl Fn(&V) -> TestCaseResult, value: &V) -> TestCaseResult {
+        match panic::catch_unwind(AssertUnwindSafe(|| test(value))) {
+            Ok(result) => result,
+            Err(payload) => Err(TestCaseError::fail(Self::panic_message(&payload))),
+        }
+    }
+
+    fn panic_message(payload: &Box<dyn Any + Send>) -> String {
+        if let Some(message) = payload.downcast_ref::<&str>() {
+            (*message).to_string()
+        } else if let Some(message) = payload.downcast_ref::<String>() {
+            message.clone()
+        } else {
+            "test panicked with a non-string payload".to_string()
+        }
+    }
+}
+
+impl fmt::Display for TestRunner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "\tconfig = {:?}", self.config)
+    }
+}
@@ -0,0 +1,79 @@
+//-
+// Copyright 2024 The proptest developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use super::runner::TestRunner;
+use crate::strategy::{Strategy, ValueTree};
+
+/// Configuration for [`check_strategy_sanity`].
+#[derive(Clone, Debug)]
+pub struct CheckStrategySanityOptions {
+    /// How many independently generated trees to exercise.
+    pub cases: u32,
+}
+
+impl Default for CheckStrategySanityOptions {
+    fn default() -> Self {
+        CheckStrategySanityOptions { cases: 64 }
+    }
+}
+
+/// Exercises a `Strategy`'s `new_tree`/`simplify`/`complicate` cycle against
+/// a handful of generated trees, checking the invariant every hand-written
+/// `ValueTree` is expected to uphold: simplifying all the way down, then
+/// complicating the same number of times, returns to the original value.
+///
+/// A `ValueTree` that violates this (e.g. because `simplify` never actually
+/// moves `lo`/`hi` towards `cur`) either loops forever here or fails the
+/// final `assert_eq!`, rather than silently shrinking to the wrong answer
+/// only when a real test happens to fail.
+///
+/// Intended for use in a strategy's own test module, in the same spirit as
+/// `proptest!`'s tests, but for exercising the strategy itself rather than
+/// a property over its generated values.
+pub fn check_strategy_sanity<S: Strategy>(
+    strategy: S,
+    options: Option<CheckStrategySanityOptions>,
+) where
+    S::Value: PartialEq,
+{
+    const MAX_SIMPLIFY_STEPS: u32 = 1_000_000;
+
+    let options = options.unwrap_or_default();
+    let mut runner = TestRunner::deterministic();
+
+    for _ in 0..options.cases {
+        let mut tree = strategy
+            .new_tree(&mut runner)
+            .expect("strategy refused to generate a value");
+        let initial = tree.current();
+
+        let mut simplify_steps = 0u32;
+        while tree.simplify() {
+            simplify_steps += 1;
+            assert!(
+                simplify_steps < MAX_SIMPLIFY_STEPS,
+                "simplify() did not terminate within {} steps",
+                MAX_SIMPLIFY_STEPS
+            );
+        }
+
+        for _ in 0..simplify_steps {
+            assert!(
+                tree.complicate(),
+                "complicate() could not undo a prior simplify() step"
+            );
+        }
+
+        assert!(
+            initial == tree.current(),
+            "complicate() did not restore the original value after undoing \
+             every simplify() step"
+        );
+    }
+}
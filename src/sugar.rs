@@ -137,6 +137,56 @@ macro_rules! prop_assume {
     };
 }
 
+/// Defines a `cargo-fuzz`/libFuzzer entry point that evaluates a `Strategy`
+/// (or, by default, `any::<$ty>()`) against the raw bytes the fuzzer hands
+/// it.
+///
+/// This lets the exact strategies used in `proptest!` tests also serve as
+/// coverage-guided fuzz targets: the fuzzer's corpus is fed to
+/// [`TestRunner::from_fuzz_bytes`](crate::test_runner::TestRunner::from_fuzz_bytes),
+/// which drives generation deterministically from those bytes instead of a
+/// PRNG, so a fuzzer-discovered crashing input can be replayed exactly.
+///
+/// Example:
+///
+/// ```ignore
+/// #[macro_use] extern crate proptest;
+///
+/// proptest_fuzz_target!(|v: Vec<u8>| {
+///     let _ = my_crate::parse(&v);
+/// });
+/// ```
+///
+/// or, to drive a specific strategy rather than the type's `Arbitrary` impl:
+///
+/// ```ignore
+/// proptest_fuzz_target!(v in prop::collection::vec_take_rest(0u8..16, 0..64) => {
+///     let _ = my_crate::parse(&v);
+/// });
+/// ```
+#[macro_export]
+macro_rules! proptest_fuzz_target {
+    (|$parm:ident: $ty:ty| $body:block) => {
+        proptest_fuzz_target!($parm in $crate::arbitrary::any::<$ty>() => $body);
+    };
+
+    ($parm:pat in $strategy:expr => $body:block) => {
+        /// Entry point called once per fuzzer input, under the name that
+        /// `cargo fuzz`'s libFuzzer glue calls.
+        pub fn rust_fuzzer_test_input(bytes: &[u8]) {
+            let mut runner =
+                $crate::test_runner::TestRunner::from_fuzz_bytes(bytes);
+            let strategy = $strategy;
+            if let Ok(tree) =
+                $crate::strategy::Strategy::new_tree(&strategy, &mut runner)
+            {
+                let $parm = $crate::strategy::ValueTree::current(&tree);
+                $body
+            }
+        }
+    };
+}
+
 #[cfg(test)]
 mod test {
     proptest! {
@@ -146,4 +196,72 @@ mod test {
             assert!(a + b < 50);
         }
     }
-}
\ No newline at end of file
+
+    /// Exercises `proptest_fuzz_target!`'s replay determinism directly,
+    /// without depending on any `Arbitrary` impl: the strategy below just
+    /// hands back the first byte of whatever buffer it's given.
+    mod fuzz_target_replay {
+        use core::sync::atomic::{AtomicU8, Ordering};
+
+        use rand::RngCore;
+
+        use crate::strategy::size_hint::{self, SizeHint};
+        use crate::strategy::{NewTree, Strategy, ValueTree};
+        use crate::test_runner::TestRunner;
+
+        static SEEN: AtomicU8 = AtomicU8::new(0);
+
+        #[derive(Clone, Copy, Debug)]
+        struct FirstByteStrategy;
+
+        #[derive(Clone, Copy, Debug)]
+        struct FirstByteTree(u8);
+
+        impl Strategy for FirstByteStrategy {
+            type Tree = FirstByteTree;
+            type Value = u8;
+
+            fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+                let mut byte = [0u8; 1];
+                runner.rng().fill_bytes(&mut byte);
+                Ok(FirstByteTree(byte[0]))
+            }
+
+            fn size_hint(&self) -> SizeHint {
+                size_hint::exact(1)
+            }
+        }
+
+        impl ValueTree for FirstByteTree {
+            type Value = u8;
+
+            fn current(&self) -> u8 {
+                self.0
+            }
+
+            fn simplify(&mut self) -> bool {
+                false
+            }
+
+            fn complicate(&mut self) -> bool {
+                false
+            }
+        }
+
+        proptest_fuzz_target!(b in FirstByteStrategy => {
+            SEEN.store(b, Ordering::SeqCst);
+        });
+
+        #[test]
+        fn replays_deterministically_from_fixed_bytes() {
+            rust_fuzzer_test_input(&[0x2a]);
+            assert_eq!(0x2a, SEEN.load(Ordering::SeqCst));
+        }
+
+        #[test]
+        fn empty_input_terminates_with_a_zero_filled_value() {
+            rust_fuzzer_test_input(&[]);
+            assert_eq!(0, SEEN.load(Ordering::SeqCst));
+        }
+    }
+}